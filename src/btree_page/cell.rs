@@ -1,4 +1,4 @@
-use super::{Header, page::Page};
+use super::{Header, page::Page, schema_layer::Record};
 
 /// Table B-Tree Leaf Cell (header 0x0d):
 ///
@@ -57,7 +57,25 @@ macro_rules! read_varint_and_advance {
   }};
 }
 
+macro_rules! read_row_id_and_advance {
+  ($page:expr, $offset:expr) => {{
+    let (value, varint_len) = Cell::read_row_id(&$page, $offset);
+    $offset += varint_len;
+    value
+  }};
+}
+
 impl Cell {
+  /// Decodes a row ID varint at `offset` as its raw bit pattern
+  /// reinterpreted as `u64`, rather than `Page::read_varint`'s unsigned
+  /// accumulation - a row ID is a signed 64-bit SQLite integer and the
+  /// 9-byte varint form is needed to recover negative values correctly.
+  fn read_row_id(page: &Page, offset: usize) -> (u64, usize) {
+    let (value, len) =
+      Record::parse_varint_i64(&page.data[offset..]).expect("row ID varint");
+    (value as u64, len)
+  }
+
   pub fn payload(&self) -> &[u8] {
     match self {
       Cell::TableLeaf { payload, .. } => payload,
@@ -71,11 +89,12 @@ impl Cell {
     let mut cells = Vec::new();
     let offset_adjustment = if page.page_number == 1 { 100 } else { 0 };
     for i in 0..header.num_cells {
-      let mut cell_offset = page.read_u16(8 + (i as usize) * 2) as usize - offset_adjustment;
+      let mut cell_offset =
+        page.read_u16(header.header_size + (i as usize) * 2) as usize - offset_adjustment;
       match header.page_type {
         0x0D => {
           let payload_size = read_varint_and_advance!(page, cell_offset);
-          let row_id = read_varint_and_advance!(page, cell_offset);
+          let row_id = read_row_id_and_advance!(page, cell_offset);
           let (payload, overflow_page) = Self::read_payload(&page, payload_size, cell_offset);
           cells.push(Cell::TableLeaf {
             payload_size,
@@ -87,7 +106,7 @@ impl Cell {
         0x05 => {
           cells.push(Cell::TableInterior {
             left_child_page: page.read_u32(cell_offset),
-            row_id: page.read_varint(cell_offset + 4).0,
+            row_id: Self::read_row_id(&page, cell_offset + 4).0,
           });
         }
         0x0A => {
@@ -62,7 +62,12 @@ pub struct Header {
   pub num_cells: u16,
   pub start_cell_content: u16,
   pub num_fragmented_free_bytes: u8,
-  pub right_most_pointer: u32,
+  /// Only present on interior pages (`0x02`/`0x05`); on leaf pages those four
+  /// bytes are actually the start of the cell pointer array, not a pointer.
+  pub right_most_pointer: Option<u32>,
+  /// 12 bytes for interior pages (the extra 4 being `right_most_pointer`),
+  /// 8 bytes for leaf pages. The cell pointer array begins right after it.
+  pub header_size: usize,
 }
 
 impl Header {
@@ -72,7 +77,9 @@ impl Header {
     let num_cells = page.read_u16(3);
     let start_cell_content = page.read_u16(5);
     let num_fragmented_free_bytes = page.read_u8(7);
-    let right_most_pointer = page.read_u32(8);
+    let is_interior = matches!(page_type, 0x02 | 0x05);
+    let right_most_pointer = is_interior.then(|| page.read_u32(8));
+    let header_size = if is_interior { 12 } else { 8 };
 
     println!(
       r#"
@@ -81,7 +88,7 @@ impl Header {
       num_cells: {num_cells}
       start_cell_content: {start_cell_content}
       num_fragmented_free_bytes: {num_fragmented_free_bytes}
-      right_most_pointer:{right_most_pointer}
+      right_most_pointer:{right_most_pointer:?}
     "#
     );
 
@@ -92,6 +99,7 @@ impl Header {
       start_cell_content,
       num_fragmented_free_bytes,
       right_most_pointer,
+      header_size,
     }
   }
 }
@@ -5,6 +5,8 @@ use std::{
 };
 
 use crate::btree_page::schema_layer::Record;
+use crate::dbheader::TextEncoding;
+use crate::varint::VarInt;
 use anyhow::Result;
 
 #[derive(Debug, Clone)]
@@ -15,7 +17,7 @@ pub struct Page {
 }
 
 impl Page {
-  pub fn try_from_file(file: &mut File, page_number: u32, page_size: u16) -> Result<Self> {
+  pub fn try_from_file(file: &mut File, page_number: u32, page_size: u32) -> Result<Self> {
     let offset = if page_number == 1 {
       100
     } else {
@@ -31,6 +33,28 @@ impl Page {
     })
   }
 
+  /// Builds a page from already-read bytes - e.g. a pre-image recovered
+  /// from a rollback journal - instead of reading the main database file.
+  /// `data` is the raw physical page content; for page 1 that means the
+  /// leading 100-byte database header is stripped and the buffer
+  /// zero-padded back out to a full page, matching what `try_from_file`
+  /// hands the b-tree layer for page 1.
+  pub fn from_bytes(mut data: Vec<u8>, page_number: u32) -> Self {
+    let offset = if page_number == 1 {
+      let page_size = data.len();
+      data.drain(0..100);
+      data.resize(page_size, 0);
+      100
+    } else {
+      0
+    };
+    Self {
+      data,
+      offset,
+      page_number,
+    }
+  }
+
   fn at(&self, offset: usize) -> u8 {
     self.data[offset]
   }
@@ -61,30 +85,10 @@ impl Page {
   }
 
   pub fn read_varint(&self, offset: usize) -> (u64, usize) {
-    let mut value = 0u64;
-    let mut shift = 0;
-    let mut size = 0;
-
-    for i in 0..9 {
-      let byte = self.at(offset + i);
-      size += 1;
-
-      if i == 8 {
-        value |= (byte as u64) << shift;
-        break;
-      } else {
-        value |= ((byte & 0x7F) as u64) << shift;
-        if (byte & 0x80) == 0 {
-          break;
-        }
-      }
-      shift += 7;
-    }
-
-    (value, size)
+    VarInt::decode(&self.data[offset..])
   }
 
-  pub fn parse_table_leaf_page(&self) -> Vec<Record> {
+  pub fn parse_table_leaf_page(&self, encoding: TextEncoding) -> Vec<Record> {
     let mut records = Vec::new();
     let num_cells = u16::from_be_bytes([self.data[3], self.data[4]]);
 
@@ -93,7 +97,7 @@ impl Page {
       let cell_offset =
         u16::from_be_bytes([self.data[offset_index], self.data[offset_index + 1]]) as usize;
 
-      let record = Record::parse(&self.data[cell_offset..]).expect("This to work");
+      let record = Record::parse(&self.data[cell_offset..], encoding).expect("This to work");
       records.push(record);
     }
 
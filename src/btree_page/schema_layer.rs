@@ -1,5 +1,8 @@
+use crate::dbheader::TextEncoding;
+use crate::varint::VarInt;
 use anyhow::{Result, anyhow};
 use std::{
+  borrow::Cow,
   convert::TryInto,
   fmt::{self, Display, Formatter},
 };
@@ -39,6 +42,12 @@ pub enum Value {
   Float(f64),
   Blob(Vec<u8>),
   Text(String),
+  /// A 128-bit integer, opt-in and stored on disk as an ordinary 16-byte
+  /// blob (see `encode_i128_blob`) - SQLite itself has no native type wide
+  /// enough, so this never round-trips through `serial_type`/`parse_value`
+  /// automatically; callers that know a column holds one reconstruct it
+  /// with `as_integer128`.
+  Integer128(i128),
 }
 
 impl Value {
@@ -69,6 +78,278 @@ impl Value {
       _ => panic!("Value is not a text"),
     }
   }
+
+  /// Reconstructs a 128-bit integer from a value holding one: either an
+  /// in-memory `Integer128` directly, or a 16-byte `Blob` read back off
+  /// disk - the two are indistinguishable there, so this is an opt-in
+  /// reinterpretation for columns the caller already knows hold one.
+  /// Returns `None` for anything else, including blobs of any other length.
+  pub fn as_integer128(&self) -> Option<i128> {
+    match self {
+      Value::Integer128(value) => Some(*value),
+      Value::Blob(bytes) => bytes.as_slice().try_into().ok().map(Self::decode_i128_blob),
+      _ => None,
+    }
+  }
+
+  /// Encodes a 128-bit integer as a 16-byte big-endian blob with its most
+  /// significant bit flipped: two's complement already sorts negatives and
+  /// positives monotonically within themselves, but places negatives in the
+  /// upper half of the unsigned range, above all positives. Flipping the
+  /// sign bit swaps the two halves so plain `memcmp` (e.g. as a `Blob`
+  /// index key) orders negatives before positives, matching `i128`'s own
+  /// order. The representation is big-endian and bit-exact regardless of
+  /// host architecture.
+  pub fn encode_i128_blob(value: i128) -> [u8; 16] {
+    ((value as u128) ^ (1u128 << 127)).to_be_bytes()
+  }
+
+  /// Reverses `encode_i128_blob`.
+  fn decode_i128_blob(bytes: [u8; 16]) -> i128 {
+    (u128::from_be_bytes(bytes) ^ (1u128 << 127)) as i128
+  }
+}
+
+impl Value {
+  /// The serial type this value would be stored as, along with the number
+  /// of body bytes it occupies, per the record-format chart above. Picks
+  /// the smallest integer width that holds the value, and the compact
+  /// zero-byte encodings for 0 and 1.
+  fn serial_type(&self) -> (u64, usize) {
+    match self {
+      Value::Null => (0, 0),
+      Value::Integer(0) => (8, 0),
+      Value::Integer(1) => (9, 0),
+      Value::Integer(value) => match *value {
+        v if v >= i8::MIN as i64 && v <= i8::MAX as i64 => (1, 1),
+        v if v >= i16::MIN as i64 && v <= i16::MAX as i64 => (2, 2),
+        v if (-(1 << 23)..(1 << 23)).contains(&v) => (3, 3),
+        v if v >= i32::MIN as i64 && v <= i32::MAX as i64 => (4, 4),
+        v if (-(1 << 47)..(1 << 47)).contains(&v) => (5, 6),
+        _ => (6, 8),
+      },
+      Value::Float(_) => (7, 8),
+      Value::Blob(value) => (2 * value.len() as u64 + 12, value.len()),
+      Value::Text(value) => (2 * value.len() as u64 + 13, value.len()),
+      Value::Integer128(_) => (2 * 16 + 12, 16),
+    }
+  }
+
+  /// Encodes this value's body bytes (everything but its serial type),
+  /// sized to match `serial_type`'s content size.
+  fn serialize_body(&self) -> Vec<u8> {
+    match self {
+      Value::Null | Value::Integer(0) | Value::Integer(1) => Vec::new(),
+      Value::Integer(value) => match self.serial_type().1 {
+        1 => vec![*value as i8 as u8],
+        2 => (*value as i16).to_be_bytes().to_vec(),
+        3 => (*value as i32).to_be_bytes()[1..].to_vec(),
+        4 => (*value as i32).to_be_bytes().to_vec(),
+        6 => value.to_be_bytes()[2..].to_vec(),
+        8 => value.to_be_bytes().to_vec(),
+        _ => unreachable!("serial_type only returns sizes 1/2/3/4/6/8 for integers"),
+      },
+      Value::Float(value) => value.to_be_bytes().to_vec(),
+      Value::Blob(value) => value.clone(),
+      Value::Text(value) => value.as_bytes().to_vec(),
+      Value::Integer128(value) => Self::encode_i128_blob(*value).to_vec(),
+    }
+  }
+
+  /// Appends a memcmp-sortable encoding of this value to `buf`, for use as
+  /// an index b-tree key: a 1-byte type tag establishes SQLite's
+  /// cross-type collation order (NULL < numeric < Text < Blob), and each
+  /// tag's body is chosen so that byte-lexicographic order on the encoding
+  /// matches this value's logical order. `Integer` and `Float` share a tag
+  /// and are both encoded as an ordered `f64`, so a key built this way
+  /// loses integer precision beyond 2^53 - an accepted tradeoff for
+  /// comparing the two numeric variants byte-for-byte.
+  pub fn encode_ordered(&self, buf: &mut Vec<u8>) {
+    match self {
+      Value::Null => buf.push(0),
+      Value::Integer(value) => {
+        buf.push(1);
+        buf.extend(Self::ordered_f64_bits(*value as f64));
+      }
+      Value::Float(value) => {
+        buf.push(1);
+        buf.extend(Self::ordered_f64_bits(*value));
+      }
+      Value::Text(value) => {
+        buf.push(2);
+        Self::encode_escaped_bytes(value.as_bytes(), buf);
+      }
+      Value::Blob(value) => {
+        buf.push(3);
+        Self::encode_escaped_bytes(value, buf);
+      }
+      Value::Integer128(value) => {
+        buf.push(3);
+        Self::encode_escaped_bytes(&Self::encode_i128_blob(*value), buf);
+      }
+    }
+  }
+
+  /// Transforms an `f64`'s bits so that unsigned big-endian byte order
+  /// matches IEEE-754 order: flip every bit for negatives (so larger
+  /// magnitude sorts lower, and negatives sort below positives), or just
+  /// the sign bit for non-negatives (so they still sort above negatives).
+  fn ordered_f64_bits(value: f64) -> [u8; 8] {
+    let bits = value.to_bits();
+    let ordered = if bits & 0x8000_0000_0000_0000 != 0 {
+      !bits
+    } else {
+      bits | 0x8000_0000_0000_0000
+    };
+    ordered.to_be_bytes()
+  }
+
+  /// Appends `bytes` with every `0x00` escaped to `0x00 0xFF`, terminated
+  /// by `0x00 0x00` - so a value always sorts before any extension of
+  /// itself, which a bare NUL terminator couldn't guarantee for byte
+  /// strings that themselves contain NUL bytes.
+  fn encode_escaped_bytes(bytes: &[u8], buf: &mut Vec<u8>) {
+    for &byte in bytes {
+      if byte == 0x00 {
+        buf.extend([0x00, 0xFF]);
+      } else {
+        buf.push(byte);
+      }
+    }
+    buf.extend([0x00, 0x00]);
+  }
+
+  /// Decodes one value previously written by `encode_ordered`, returning it
+  /// together with the number of bytes consumed from `data`. Numeric keys
+  /// always decode back as `Value::Float`, matching the shared encoding
+  /// `encode_ordered` uses for `Integer` and `Float`.
+  pub fn decode_ordered(data: &[u8]) -> Result<(Value, usize)> {
+    match data.first() {
+      Some(0) => Ok((Value::Null, 1)),
+      Some(1) => {
+        let bits = u64::from_be_bytes(data[1..9].try_into()?);
+        let restored = if bits & 0x8000_0000_0000_0000 != 0 {
+          bits & !0x8000_0000_0000_0000
+        } else {
+          !bits
+        };
+        Ok((Value::Float(f64::from_bits(restored)), 9))
+      }
+      Some(2) => {
+        let (bytes, len) = Self::decode_escaped_bytes(&data[1..])?;
+        Ok((Value::Text(String::from_utf8(bytes)?), 1 + len))
+      }
+      Some(3) => {
+        let (bytes, len) = Self::decode_escaped_bytes(&data[1..])?;
+        Ok((Value::Blob(bytes), 1 + len))
+      }
+      _ => Err(anyhow!("Unknown ordered-encoding type tag")),
+    }
+  }
+
+  /// Reverses `encode_escaped_bytes`, returning the unescaped bytes and the
+  /// number of encoded bytes (including the `0x00 0x00` terminator)
+  /// consumed.
+  fn decode_escaped_bytes(data: &[u8]) -> Result<(Vec<u8>, usize)> {
+    let mut bytes = Vec::new();
+    let mut offset = 0;
+
+    loop {
+      match data.get(offset..offset + 2) {
+        Some([0x00, 0x00]) => return Ok((bytes, offset + 2)),
+        Some([0x00, 0xFF]) => {
+          bytes.push(0x00);
+          offset += 2;
+        }
+        _ => {
+          bytes.push(data[offset]);
+          offset += 1;
+        }
+      }
+    }
+  }
+}
+
+/// A TEXT collating sequence, selectable per comparison independently of
+/// `Value`'s default (BINARY) `Ord` implementation - e.g. a column declared
+/// `COLLATE NOCASE` needs its comparisons run through `Collation::NoCase`
+/// rather than `Value::cmp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collation {
+  /// Compares byte-for-byte (`memcmp`). SQLite's default.
+  Binary,
+  /// Compares case-insensitively (uppercased byte-for-byte).
+  NoCase,
+  /// Compares after stripping trailing whitespace from both sides.
+  RTrim,
+}
+
+impl Collation {
+  fn compare(&self, left: &str, right: &str) -> std::cmp::Ordering {
+    match self {
+      Collation::Binary => left.cmp(right),
+      Collation::NoCase => left.to_uppercase().cmp(&right.to_uppercase()),
+      Collation::RTrim => left.trim_end().cmp(right.trim_end()),
+    }
+  }
+}
+
+impl Value {
+  /// SQLite's storage-class rank for comparisons across types: NULL <
+  /// numeric < TEXT < BLOB.
+  fn type_rank(&self) -> u8 {
+    match self {
+      Value::Null => 0,
+      Value::Integer(_) | Value::Float(_) => 1,
+      Value::Text(_) => 2,
+      Value::Blob(_) | Value::Integer128(_) => 3,
+    }
+  }
+
+  /// Compares against `other` using SQLite's type-sort order - NULL < all
+  /// numerics (compared by mathematical value, not by `Integer`/`Float`
+  /// variant) < TEXT < BLOB (`memcmp`) - with TEXT-to-TEXT comparisons run
+  /// through `collation`. `Value::cmp` is the same comparison under
+  /// `Collation::Binary`, for callers that just need the default ordering.
+  pub fn compare_with(&self, other: &Value, collation: Collation) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    match (self, other) {
+      (Value::Integer(a), Value::Integer(b)) => a.cmp(b),
+      (Value::Float(a), Value::Float(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+      (Value::Integer(a), Value::Float(b)) => {
+        (*a as f64).partial_cmp(b).unwrap_or(Ordering::Equal)
+      }
+      (Value::Float(a), Value::Integer(b)) => {
+        a.partial_cmp(&(*b as f64)).unwrap_or(Ordering::Equal)
+      }
+      (Value::Text(a), Value::Text(b)) => collation.compare(a, b),
+      (Value::Blob(a), Value::Blob(b)) => a.cmp(b),
+      (Value::Integer128(a), Value::Integer128(b)) => a.cmp(b),
+      (Value::Integer128(a), Value::Blob(b)) => Self::encode_i128_blob(*a).as_slice().cmp(b),
+      (Value::Blob(a), Value::Integer128(b)) => a.as_slice().cmp(Self::encode_i128_blob(*b).as_slice()),
+      _ => self.type_rank().cmp(&other.type_rank()),
+    }
+  }
+}
+
+impl PartialEq for Value {
+  fn eq(&self, other: &Self) -> bool {
+    self.compare_with(other, Collation::Binary) == std::cmp::Ordering::Equal
+  }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Value {
+  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    self.compare_with(other, Collation::Binary)
+  }
 }
 
 impl Display for Value {
@@ -79,25 +360,108 @@ impl Display for Value {
       Value::Float(value) => write!(f, "{}", value),
       Value::Blob(value) => write!(f, "{:?}", value),
       Value::Text(value) => write!(f, "{}", value),
+      Value::Integer128(value) => write!(f, "{}", value),
+    }
+  }
+}
+
+/// A zero-copy counterpart to `Value`: `Blob` borrows straight from the page
+/// buffer, and `Text` does too whenever the database's encoding is already
+/// UTF-8 (falling back to an owned `String` for UTF-16, which must be
+/// transcoded). Produced by `Record::parse_borrowed` for read-heavy scans
+/// that don't want to allocate a `Vec`/`String` per cell just to inspect or
+/// compare values.
+#[derive(Debug, Clone)]
+pub enum ValueRef<'a> {
+  Null,
+  Integer(i64),
+  Float(f64),
+  Blob(&'a [u8]),
+  Text(Cow<'a, str>),
+}
+
+impl<'a> ValueRef<'a> {
+  /// Copies this value's borrowed bytes (if any) into an owned `Value`.
+  pub fn to_owned(&self) -> Value {
+    match self {
+      ValueRef::Null => Value::Null,
+      ValueRef::Integer(value) => Value::Integer(*value),
+      ValueRef::Float(value) => Value::Float(*value),
+      ValueRef::Blob(value) => Value::Blob(value.to_vec()),
+      ValueRef::Text(value) => Value::Text(value.clone().into_owned()),
+    }
+  }
+}
+
+/// The zero-copy counterpart to `Record`, produced by `Record::parse_borrowed`.
+#[derive(Debug, Clone)]
+pub struct RecordRef<'a> {
+  pub values: Vec<ValueRef<'a>>,
+}
+
+impl<'a> RecordRef<'a> {
+  /// Copies every value into an owned `Record`, for callers that need to
+  /// outlive the page buffer this record borrows from.
+  pub fn to_owned(&self) -> Record {
+    Record {
+      values: self.values.iter().map(ValueRef::to_owned).collect(),
     }
   }
 }
 
 impl Record {
-  pub fn parse(data: &[u8]) -> Result<Self> {
+  /// Serializes these values into SQLite's on-disk record format - the
+  /// inverse of `parse` - mirroring `OP_MakeRecord`: each value's serial
+  /// type is written as a header varint, then the header (prefixed with its
+  /// own length as a varint) is followed by the concatenated value bodies.
+  /// Text is always written as UTF-8, since an in-memory `Value::Text`
+  /// doesn't track the source encoding the way a value read from a
+  /// database does.
+  pub fn serialize(&self) -> Vec<u8> {
+    let serial_types: Vec<u64> = self.values.iter().map(|v| v.serial_type().0).collect();
+
+    let mut header_body = Vec::new();
+    for serial_type in &serial_types {
+      header_body.extend(VarInt::encode(*serial_type));
+    }
+
+    // The size varint counts its own encoded length, so growing it (e.g.
+    // 1 byte -> 2 bytes once the header crosses 127 bytes) can change the
+    // very size it's reporting. Iterate to a fixed point instead of
+    // assuming a single pass lands on a consistent size.
+    let mut header_size = header_body.len();
+    loop {
+      let candidate = VarInt::get_encoded_size(header_size as u64) + header_body.len();
+      if candidate == header_size {
+        break;
+      }
+      header_size = candidate;
+    }
+
+    let mut record = VarInt::encode(header_size as u64);
+    record.extend(header_body);
+
+    for value in &self.values {
+      record.extend(value.serialize_body());
+    }
+
+    record
+  }
+
+  pub fn parse(data: &[u8], encoding: TextEncoding) -> Result<Self> {
     let (header_size, header_size_len) = Self::parse_varint(data)?;
     let mut offset = header_size_len;
 
     let mut serial_types = Vec::new();
     while offset < header_size {
-      let (serial_type, serial_type_len) = Self::parse_varint(&data[offset..])?;
-      serial_types.push(serial_type);
+      let (serial_type, serial_type_len) = Self::parse_varint_i64(&data[offset..])?;
+      serial_types.push(serial_type as usize);
       offset += serial_type_len;
     }
 
     let mut values = Vec::new();
     for &serial_type in &serial_types {
-      let (value, value_len) = Self::parse_value(serial_type, &data[offset..])?;
+      let (value, value_len) = Self::parse_value(serial_type, &data[offset..], encoding)?;
       values.push(value);
       offset += value_len;
     }
@@ -105,25 +469,72 @@ impl Record {
     Ok(Record { values })
   }
 
+  /// Same traversal as `parse`, but borrows blob and (UTF-8) text bodies
+  /// directly from `data` instead of copying them into a `Vec`/`String` -
+  /// see `ValueRef`.
+  pub fn parse_borrowed(data: &[u8], encoding: TextEncoding) -> Result<RecordRef<'_>> {
+    let (header_size, header_size_len) = Self::parse_varint(data)?;
+    let mut offset = header_size_len;
+
+    let mut serial_types = Vec::new();
+    while offset < header_size {
+      let (serial_type, serial_type_len) = Self::parse_varint_i64(&data[offset..])?;
+      serial_types.push(serial_type as usize);
+      offset += serial_type_len;
+    }
+
+    let mut values = Vec::new();
+    for &serial_type in &serial_types {
+      let (value, value_len) = Self::parse_value_ref(serial_type, &data[offset..], encoding)?;
+      values.push(value);
+      offset += value_len;
+    }
+
+    Ok(RecordRef { values })
+  }
+
+  /// Decodes a varint whose value only ever needs to index into the record
+  /// (the header-size prefix): always non-negative in practice, so the
+  /// `parse_varint_i64` result is cast to `usize` here and callers don't
+  /// have to cast it themselves.
   fn parse_varint(data: &[u8]) -> Result<(usize, usize)> {
-    let mut value = 0usize;
-    let mut length = 0;
+    let (value, len) = Self::parse_varint_i64(data)?;
+    Ok((value as usize, len))
+  }
 
-    for &byte in data.iter() {
-      value = (value << 7) | (byte & 0x7F) as usize;
-      length += 1;
+  /// Decodes a SQLite varint (1-9 bytes) into its full signed 64-bit value:
+  /// the first 8 bytes contribute 7 bits each, high bit signalling another
+  /// byte follows, but the 9th byte (if reached) contributes all 8 of its
+  /// bits rather than 7 - the one-byte-wider final byte is what lets a
+  /// varint reach a full 64-bit twos-complement value instead of only 63.
+  /// Used for serial types and row IDs, both of which are stored as a
+  /// varint's raw bit pattern reinterpreted as `i64`.
+  pub(crate) fn parse_varint_i64(data: &[u8]) -> Result<(i64, usize)> {
+    let mut value = 0u64;
+
+    for i in 0..9 {
+      let Some(&byte) = data.get(i) else {
+        return Err(anyhow!("Invalid varint: ran out of bytes"));
+      };
+
+      if i == 8 {
+        value = (value << 8) | byte as u64;
+        return Ok((value as i64, 9));
+      }
+
+      value = (value << 7) | (byte & 0x7F) as u64;
       if byte & 0x80 == 0 {
-        return Ok((value, length));
+        return Ok((value as i64, i + 1));
       }
     }
 
-    Err(anyhow!("Invalid varint"))
+    unreachable!("loop always returns by the 9th iteration")
   }
 
-  fn parse_value(serial_type: usize, data: &[u8]) -> Result<(Value, usize)> {
+  fn parse_value(serial_type: usize, data: &[u8], encoding: TextEncoding) -> Result<(Value, usize)> {
     match serial_type {
       0 => Ok((Value::Null, 0)),
-      1 => Ok((Value::Integer(data[0] as i64), 1)),
+      1 => Ok((Value::Integer(data[0] as i8 as i64), 1)),
       2 => {
         let value = i16::from_be_bytes(data[..2].try_into()?);
         Ok((Value::Integer(value as i64), 2))
@@ -162,10 +573,192 @@ impl Record {
       }
       n if n >= 13 && n % 2 == 1 => {
         let size = (n - 13) / 2;
-        let value = String::from_utf8(data[..size].to_vec())?;
+        let value = Self::decode_text(&data[..size], encoding)?;
         Ok((Value::Text(value), size))
       }
       _ => Err(anyhow!("Unknown serial type")),
     }
   }
+
+  /// The `ValueRef` counterpart to `parse_value`: identical serial-type
+  /// decoding, except blobs borrow their bytes from `data` and UTF-8 text
+  /// borrows its `&str` instead of copying into a `Vec`/`String`. Other text
+  /// encodings still allocate, since transcoding can't be zero-copy.
+  fn parse_value_ref(serial_type: usize, data: &[u8], encoding: TextEncoding) -> Result<(ValueRef<'_>, usize)> {
+    match serial_type {
+      n if n >= 12 && n % 2 == 0 => {
+        let size = (n - 12) / 2;
+        Ok((ValueRef::Blob(&data[..size]), size))
+      }
+      n if n >= 13 && n % 2 == 1 => {
+        let size = (n - 13) / 2;
+        let text = match encoding {
+          TextEncoding::Utf8 => Cow::Borrowed(std::str::from_utf8(&data[..size])?),
+          TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+            Cow::Owned(Self::decode_text(&data[..size], encoding)?)
+          }
+        };
+        Ok((ValueRef::Text(text), size))
+      }
+      _ => {
+        let (value, len) = Self::parse_value(serial_type, data, encoding)?;
+        Ok((
+          match value {
+            Value::Null => ValueRef::Null,
+            Value::Integer(v) => ValueRef::Integer(v),
+            Value::Float(v) => ValueRef::Float(v),
+            Value::Blob(_) | Value::Text(_) | Value::Integer128(_) => unreachable!("handled above"),
+          },
+          len,
+        ))
+      }
+    }
+  }
+
+  /// Decodes a TEXT serial type's raw bytes according to the database's
+  /// text encoding, rather than assuming UTF-8.
+  fn decode_text(data: &[u8], encoding: TextEncoding) -> Result<String> {
+    match encoding {
+      TextEncoding::Utf8 => Ok(String::from_utf8(data.to_vec())?),
+      TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+        if !data.len().is_multiple_of(2) {
+          return Err(anyhow!(
+            "UTF-16 text column has an odd length of {} bytes",
+            data.len()
+          ));
+        }
+        let code_units: Vec<u16> = data
+          .chunks_exact(2)
+          .map(|pair| match encoding {
+            TextEncoding::Utf16Le => u16::from_le_bytes([pair[0], pair[1]]),
+            _ => u16::from_be_bytes([pair[0], pair[1]]),
+          })
+          .collect();
+        Ok(String::from_utf16(&code_units)?)
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn serialize_round_trips_through_parse() {
+    let record = Record {
+      values: vec![
+        Value::Null,
+        Value::Integer(0),
+        Value::Integer(1),
+        Value::Integer(123),
+        Value::Integer(-1),
+        Value::Integer(i64::MIN),
+        Value::Float(2.5),
+        Value::Text("hello".to_string()),
+        Value::Blob(vec![9, 8, 7]),
+      ],
+    };
+
+    let bytes = record.serialize();
+    let parsed = Record::parse(&bytes, TextEncoding::Utf8).unwrap();
+
+    assert_eq!(parsed.values.len(), record.values.len());
+    for (original, roundtripped) in record.values.iter().zip(parsed.values.iter()) {
+      assert_eq!(original, roundtripped);
+    }
+  }
+
+  #[test]
+  fn encode_ordered_byte_order_matches_collation_order() {
+    let values = vec![
+      Value::Null,
+      Value::Integer(-5),
+      Value::Integer(10),
+      Value::Float(3.5),
+      Value::Float(-3.5),
+      Value::Text("apple".to_string()),
+      Value::Text("banana".to_string()),
+      Value::Blob(vec![1, 2, 3]),
+      Value::Blob(vec![1, 2, 3, 0]),
+    ];
+
+    let mut encoded: Vec<(Vec<u8>, usize)> = values
+      .iter()
+      .enumerate()
+      .map(|(i, value)| {
+        let mut buf = Vec::new();
+        value.encode_ordered(&mut buf);
+        (buf, i)
+      })
+      .collect();
+    encoded.sort();
+    let order_by_bytes: Vec<usize> = encoded.into_iter().map(|(_, i)| i).collect();
+
+    let mut order_by_collation: Vec<usize> = (0..values.len()).collect();
+    order_by_collation.sort_by(|&a, &b| values[a].cmp(&values[b]));
+
+    assert_eq!(order_by_bytes, order_by_collation);
+  }
+
+  #[test]
+  fn decode_ordered_reverses_encode_ordered_for_text_and_blob() {
+    for value in [
+      Value::Text("hello".to_string()),
+      Value::Blob(vec![0x00, 0xFF, 0x01]),
+    ] {
+      let mut buf = Vec::new();
+      value.encode_ordered(&mut buf);
+      let (decoded, len) = Value::decode_ordered(&buf).unwrap();
+      assert_eq!(len, buf.len());
+      assert_eq!(decoded, value);
+    }
+  }
+
+  #[test]
+  fn parse_varint_i64_handles_the_8_to_9_byte_boundary() {
+    let max_8_byte_value = (1u64 << 56) - 1;
+    let bytes = crate::varint::VarInt::encode(max_8_byte_value);
+    assert_eq!(bytes.len(), 8);
+    let (decoded, len) = Record::parse_varint_i64(&bytes).unwrap();
+    assert_eq!(len, 8);
+    assert_eq!(decoded as u64, max_8_byte_value);
+
+    let min_9_byte_value = 1u64 << 56;
+    let bytes = crate::varint::VarInt::encode(min_9_byte_value);
+    assert_eq!(bytes.len(), 9);
+    let (decoded, len) = Record::parse_varint_i64(&bytes).unwrap();
+    assert_eq!(len, 9);
+    assert_eq!(decoded as u64, min_9_byte_value);
+  }
+
+  #[test]
+  fn parse_varint_i64_recovers_negative_row_ids() {
+    for row_id in [-1i64, -42, i64::MIN] {
+      let bytes = crate::varint::VarInt::encode(row_id as u64);
+      assert_eq!(bytes.len(), 9, "a negative i64's bit pattern always needs the full 9-byte form");
+      let (decoded, len) = Record::parse_varint_i64(&bytes).unwrap();
+      assert_eq!(len, 9);
+      assert_eq!(decoded, row_id);
+    }
+  }
+
+  #[test]
+  fn integer128_blob_round_trips() {
+    for value in [i128::MIN, -1, 0, 1, i128::MAX] {
+      let blob = Value::encode_i128_blob(value);
+      let decoded = Value::Blob(blob.to_vec()).as_integer128().unwrap();
+      assert_eq!(decoded, value);
+      assert_eq!(Value::Integer128(value).as_integer128(), Some(value));
+    }
+  }
+
+  #[test]
+  fn integer128_blob_encoding_preserves_order() {
+    let ascending = [i128::MIN, -1_000_000, -1, 0, 1, 1_000_000, i128::MAX];
+    let mut encoded: Vec<[u8; 16]> = ascending.iter().map(|&v| Value::encode_i128_blob(v)).collect();
+    let original = encoded.clone();
+    encoded.sort();
+    assert_eq!(encoded, original, "memcmp order over the encoded blobs must match i128 order");
+  }
 }
@@ -2,6 +2,48 @@ use std::{fs::File, io::Read};
 
 use anyhow::{Error, Result};
 
+/// The text encoding stored at header offset 56, shared by every TEXT value
+/// in the database - schema and row data alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+  Utf8,
+  Utf16Le,
+  Utf16Be,
+}
+
+impl TryFrom<u32> for TextEncoding {
+  type Error = Error;
+
+  fn try_from(value: u32) -> Result<Self> {
+    match value {
+      1 => Ok(TextEncoding::Utf8),
+      2 => Ok(TextEncoding::Utf16Le),
+      3 => Ok(TextEncoding::Utf16Be),
+      _ => Err(anyhow::anyhow!("Unknown database text encoding: {}", value)),
+    }
+  }
+}
+
+/// The file format version stored at header offsets 18/19 - whether the
+/// database expects rollback-journal or write-ahead-log recovery. `Unknown`
+/// covers any value SQLite hasn't defined, rather than panicking on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormatVersion {
+  Legacy,
+  WriteAheadLog,
+  Unknown(u8),
+}
+
+impl From<u8> for FileFormatVersion {
+  fn from(value: u8) -> Self {
+    match value {
+      1 => FileFormatVersion::Legacy,
+      2 => FileFormatVersion::WriteAheadLog,
+      other => FileFormatVersion::Unknown(other),
+    }
+  }
+}
+
 /// Sqlite Database Header
 ///
 /// The first 100 bytes of the database file comprise the database file header. The database file header is divided into fields as shown by the table below.
@@ -36,9 +78,12 @@ use anyhow::{Error, Result};
 /// +------+----+-----------------------------------------------------------------------------------------------------------------------------------------+
 pub struct DbHeader {
   pub header: [u8; 16],
-  pub page_size: u16,
-  pub file_format_write_version: u8,
-  pub file_format_read_version: u8,
+  /// Normalized page size in bytes: the on-disk value of `1` (meaning
+  /// 65536, since that doesn't fit in the header's `u16` field) has already
+  /// been expanded here.
+  pub page_size: u32,
+  pub file_format_write_version: FileFormatVersion,
+  pub file_format_read_version: FileFormatVersion,
   pub reserved_space: u8,
   pub max_embedded_payload_fraction: u8,
   pub min_embedded_payload_fraction: u8,
@@ -60,6 +105,13 @@ pub struct DbHeader {
   pub sqlite_version_number: u32,
 }
 
+impl DbHeader {
+  pub fn text_encoding(&self) -> TextEncoding {
+    TextEncoding::try_from(self.database_text_encoding)
+      .expect("database_text_encoding was already validated when the header was parsed")
+  }
+}
+
 impl TryFrom<&mut File> for DbHeader {
   type Error = Error;
 
@@ -75,11 +127,18 @@ impl TryFrom<&[u8; 100]> for DbHeader {
   type Error = Error;
 
   fn try_from(bytes: &[u8; 100]) -> Result<Self> {
+    let raw_page_size = u16::from_be_bytes(bytes[16..18].try_into()?);
+    let page_size = if raw_page_size == 1 {
+      65536
+    } else {
+      raw_page_size as u32
+    };
+
     let header = DbHeader {
       header: bytes[0..16].try_into()?,
-      page_size: u16::from_be_bytes(bytes[16..18].try_into()?),
-      file_format_write_version: bytes[18],
-      file_format_read_version: bytes[19],
+      page_size,
+      file_format_write_version: FileFormatVersion::from(bytes[18]),
+      file_format_read_version: FileFormatVersion::from(bytes[19]),
       reserved_space: bytes[20],
       max_embedded_payload_fraction: bytes[21],
       min_embedded_payload_fraction: bytes[22],
@@ -101,12 +160,39 @@ impl TryFrom<&[u8; 100]> for DbHeader {
       sqlite_version_number: u32::from_be_bytes(bytes[96..100].try_into()?),
     };
 
-    assert!(header.page_size.is_power_of_two());
-    assert!(header.page_size >= 512 && header.page_size <= 32768);
-    assert!(header.max_embedded_payload_fraction == 64);
-    assert!(header.min_embedded_payload_fraction == 32);
-    assert!(header.leaf_payload_fraction == 32);
-    assert!(header.reserved_expansion.iter().all(|&x| x == 0));
+    if !header.page_size.is_power_of_two() || !(512..=65536).contains(&header.page_size) {
+      return Err(anyhow::anyhow!(
+        "Invalid page size: {} (must be a power of two between 512 and 65536)",
+        header.page_size
+      ));
+    }
+    if header.max_embedded_payload_fraction != 64 {
+      return Err(anyhow::anyhow!(
+        "Invalid maximum embedded payload fraction: {}",
+        header.max_embedded_payload_fraction
+      ));
+    }
+    if header.min_embedded_payload_fraction != 32 {
+      return Err(anyhow::anyhow!(
+        "Invalid minimum embedded payload fraction: {}",
+        header.min_embedded_payload_fraction
+      ));
+    }
+    if header.leaf_payload_fraction != 32 {
+      return Err(anyhow::anyhow!(
+        "Invalid leaf payload fraction: {}",
+        header.leaf_payload_fraction
+      ));
+    }
+    if !header.reserved_expansion.iter().all(|&x| x == 0) {
+      return Err(anyhow::anyhow!("Reserved expansion space must be zero"));
+    }
+    if !matches!(header.database_text_encoding, 1..=3) {
+      return Err(anyhow::anyhow!(
+        "Unknown database text encoding: {}",
+        header.database_text_encoding
+      ));
+    }
 
     Ok(header)
   }
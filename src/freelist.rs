@@ -0,0 +1,23 @@
+use crate::btree_page::page::Page;
+
+/// One freelist trunk page. The first 4 bytes hold the next trunk page
+/// number (0 if this is the last trunk in the chain), the next 4 hold how
+/// many leaf page numbers follow, and then that many 4-byte page numbers -
+/// each a free page that carries no structure of its own - fill the rest.
+pub struct FreelistTrunkPage {
+  pub next_trunk_page: u32,
+  pub leaf_pages: Vec<u32>,
+}
+
+impl FreelistTrunkPage {
+  pub fn parse(page: &Page) -> Self {
+    let next_trunk_page = page.read_u32(0);
+    let leaf_count = page.read_u32(4) as usize;
+    let leaf_pages = (0..leaf_count).map(|i| page.read_u32(8 + i * 4)).collect();
+
+    Self {
+      next_trunk_page,
+      leaf_pages,
+    }
+  }
+}
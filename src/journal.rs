@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek};
+
+use anyhow::{Error, Result};
+
+/// The 8-byte magic every valid rollback journal header starts with.
+const JOURNAL_MAGIC: [u8; 8] = [0xd9, 0xd5, 0x05, 0xf9, 0x20, 0xa1, 0x63, 0xd7];
+
+/// A hot rollback journal: the sidecar `<db>-journal` file SQLite writes
+/// before modifying a page in place, holding the pre-transaction content of
+/// every page it's about to touch so a crash mid-write can be rolled back.
+/// This only understands a single header/record segment (the common case
+/// for a journal left behind by one interrupted transaction) - a real
+/// journal can contain several such segments back to back if the writer
+/// synced more than once.
+pub struct RollbackJournal {
+  pub page_size: u32,
+  /// Pre-transaction content for every page the journal covers, keyed by
+  /// page number.
+  pub pages: HashMap<u32, Vec<u8>>,
+}
+
+impl TryFrom<&mut File> for RollbackJournal {
+  type Error = Error;
+
+  fn try_from(file: &mut File) -> Result<Self> {
+    let mut header = [0u8; 28];
+    file.read_exact(&mut header)?;
+
+    if header[0..8] != JOURNAL_MAGIC {
+      return Err(anyhow::anyhow!("Not a rollback journal: bad magic"));
+    }
+
+    let record_count = u32::from_be_bytes(header[8..12].try_into()?);
+    let sector_size = u32::from_be_bytes(header[20..24].try_into()?);
+    let page_size = u32::from_be_bytes(header[24..28].try_into()?);
+
+    if !page_size.is_power_of_two() || !(512..=65536).contains(&page_size) {
+      return Err(anyhow::anyhow!("Invalid journal page size: {}", page_size));
+    }
+
+    // The header occupies the first sector; records start at the next
+    // sector boundary so a torn write never corrupts a record.
+    let records_start = if sector_size == 0 {
+      28
+    } else {
+      28u32.div_ceil(sector_size) * sector_size
+    };
+    file.seek_relative(records_start as i64 - 28)?;
+
+    let mut pages = HashMap::new();
+    for _ in 0..record_count {
+      let mut page_number_bytes = [0u8; 4];
+      file.read_exact(&mut page_number_bytes)?;
+      let page_number = u32::from_be_bytes(page_number_bytes);
+
+      let mut page_data = vec![0u8; page_size as usize];
+      file.read_exact(&mut page_data)?;
+
+      let mut checksum = [0u8; 4];
+      file.read_exact(&mut checksum)?;
+
+      pages.insert(page_number, page_data);
+    }
+
+    Ok(RollbackJournal { page_size, pages })
+  }
+}
+
+/// Outcome of looking for the `<db>-journal` sidecar next to a database
+/// file.
+pub enum JournalLookup {
+  /// No `-journal` file exists; the database is as of its last commit.
+  Absent,
+  /// A `-journal` file exists but didn't parse as a recognizable rollback
+  /// journal, so it's left unapplied rather than risking a bad overlay.
+  Unrecognized,
+  Found(RollbackJournal),
+}
+
+impl RollbackJournal {
+  /// Looks for and parses the `-journal` sidecar of `db_path`, if any.
+  pub fn lookup(db_path: &str) -> JournalLookup {
+    let journal_path = format!("{db_path}-journal");
+    let Ok(mut file) = File::open(journal_path) else {
+      return JournalLookup::Absent;
+    };
+    match RollbackJournal::try_from(&mut file) {
+      Ok(journal) => JournalLookup::Found(journal),
+      Err(_) => JournalLookup::Unrecognized,
+    }
+  }
+}
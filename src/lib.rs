@@ -1,33 +1,76 @@
 use itertools::Itertools;
 use parser::schema;
-use parser::select::{Column, SelectStatement};
-use tracing::{debug, trace};
+use parser::select::{AggregateFn, Column, Condition, LimitClause, SelectStatement};
+use tracing::{debug, trace, warn};
 
 use crate::btree_page::BTree;
 use crate::btree_page::cell::Cell;
 use crate::btree_page::page::Page;
-use crate::btree_page::schema_layer::Record;
+use crate::btree_page::schema_layer::{Record, Value};
 use crate::dbheader::DbHeader;
+use crate::freelist::FreelistTrunkPage;
+use crate::journal::{JournalLookup, RollbackJournal};
 use std::collections::HashMap;
 use std::fs::File;
 
 pub mod btree_page;
 pub mod dbheader;
+pub mod freelist;
+pub mod journal;
 pub mod parser;
+pub mod varint;
 
 pub struct SQLite {
   file: File,
   db_header: DbHeader,
+  /// Pre-transaction page content recovered from a rollback journal via
+  /// `open_with_recovery`, overlaid onto the corresponding page whenever it
+  /// is loaded. Empty for a plain `open`.
+  recovered_pages: HashMap<u32, Vec<u8>>,
+}
+
+/// Outcome of `SQLite::open_with_recovery`, distinguishing whether a hot
+/// rollback journal was found and its pre-images applied, was simply
+/// absent (the common case), or was present but didn't parse as a
+/// recognizable journal - in which case the database is still returned,
+/// unmodified, rather than failing the whole open.
+pub enum OpenStatus {
+  Clean(SQLite),
+  Recovered(SQLite),
+  CorruptJournal(SQLite),
 }
 
 impl SQLite {
   pub fn open(path: &str) -> anyhow::Result<Self> {
     let mut file = File::open(path)?;
     let db_header = DbHeader::try_from(&mut file)?;
-    Ok(SQLite { file, db_header })
+    Ok(SQLite {
+      file,
+      db_header,
+      recovered_pages: HashMap::new(),
+    })
+  }
+
+  /// Like `open`, but additionally looks for a `<path>-journal` sidecar and,
+  /// if one is found and parses cleanly, overlays its pre-transaction page
+  /// images so the database reads as of its last committed state instead
+  /// of a crashed mid-transaction write.
+  pub fn open_with_recovery(path: &str) -> anyhow::Result<OpenStatus> {
+    let mut sqlite = Self::open(path)?;
+    match RollbackJournal::lookup(path) {
+      JournalLookup::Absent => Ok(OpenStatus::Clean(sqlite)),
+      JournalLookup::Unrecognized => Ok(OpenStatus::CorruptJournal(sqlite)),
+      JournalLookup::Found(journal) => {
+        sqlite.recovered_pages = journal.pages;
+        Ok(OpenStatus::Recovered(sqlite))
+      }
+    }
   }
 
   pub fn load_page(&mut self, page_num: u32) -> anyhow::Result<Page> {
+    if let Some(pre_image) = self.recovered_pages.get(&page_num) {
+      return Ok(Page::from_bytes(pre_image.clone(), page_num));
+    }
     Page::try_from_file(&mut self.file, page_num, self.db_header.page_size)
       .map_err(|e| anyhow::anyhow!("Failed to load page {}: {}", page_num, e))
   }
@@ -37,14 +80,261 @@ impl SQLite {
     Ok(BTree::new(page))
   }
 
+  /// Shared b-tree descent: given a root page number, an interior/leaf page
+  /// type pair, and a way to pull a cell's `left_child_page`, recursively
+  /// visits every cell on interior pages left-to-right, finally descending
+  /// into the header's `right_most_pointer`, and returns every cell found on
+  /// leaf pages. Both table and index b-trees share this traversal shape -
+  /// only the page types and the interior cell's child-pointer field differ.
+  fn collect_leaf_cells(
+    &mut self,
+    page_num: u32,
+    interior_page_type: u8,
+    leaf_page_type: u8,
+    left_child_page: fn(&Cell) -> Option<u32>,
+  ) -> anyhow::Result<Vec<Cell>> {
+    let btree = self.btree_from_page(page_num)?;
+    match btree.header.page_type {
+      t if t == leaf_page_type => Ok(btree.cells),
+      t if t == interior_page_type => {
+        let mut cells = Vec::new();
+        for cell in &btree.cells {
+          if let Some(child_page) = left_child_page(cell) {
+            cells.extend(self.collect_leaf_cells(
+              child_page,
+              interior_page_type,
+              leaf_page_type,
+              left_child_page,
+            )?);
+          }
+        }
+        let right_most_pointer = btree
+          .header
+          .right_most_pointer
+          .expect("interior page must carry a right-most pointer");
+        cells.extend(self.collect_leaf_cells(
+          right_most_pointer,
+          interior_page_type,
+          leaf_page_type,
+          left_child_page,
+        )?);
+        Ok(cells)
+      }
+      _ => anyhow::bail!(
+        "Unexpected page type 0x{:02X} for page {}",
+        btree.header.page_type,
+        page_num
+      ),
+    }
+  }
+
+  /// Walks a table b-tree rooted at `page_num`, descending through interior
+  /// (`0x05`) pages in key order - each cell's `left_child_page`, then
+  /// finally the header's `right_most_pointer` - and collecting every
+  /// `TableLeaf` cell found on the leaf (`0x0D`) pages. This is the single
+  /// entry point row-reading code should use instead of a single `BTree`'s
+  /// `cells`, since a table root page may itself be an interior page.
+  pub fn table_leaf_cells(&mut self, page_num: u32) -> anyhow::Result<Vec<Cell>> {
+    self.collect_leaf_cells(page_num, 0x05, 0x0D, |cell| match cell {
+      Cell::TableInterior {
+        left_child_page, ..
+      } => Some(*left_child_page),
+      _ => None,
+    })
+  }
+
+  /// Looks up `sqlite_master` for a `CREATE INDEX` entry on `table_name(column)`
+  /// and returns its root page, if one exists.
+  fn find_index(&mut self, table_name: &str, column: &str) -> anyhow::Result<Option<u32>> {
+    let schema_cells = self.table_leaf_cells(1)?;
+    let table_upper = table_name.to_uppercase();
+    let column_upper = column.to_uppercase();
+
+    for cell in &schema_cells {
+      if let Cell::TableLeaf { .. } = cell {
+        let record = self.record_from_cell(cell)?;
+        if record.values[0].as_text() != "index" {
+          continue;
+        }
+        if record.values[2].as_text().to_uppercase() != table_upper {
+          continue;
+        }
+        let sql = record.values[4].as_text();
+        let Ok(index_stmt) = parser::index::parse(sql) else {
+          continue;
+        };
+        if index_stmt.column.to_uppercase() == column_upper {
+          return Ok(Some(record.values[3].as_integer() as u32));
+        }
+      }
+    }
+
+    Ok(None)
+  }
+
+  /// Whether an index key column equals the (textual) probe value from a
+  /// `WHERE` clause, coercing the probe to the key's own type before
+  /// comparing.
+  fn index_key_matches(key: &Value, target: &str) -> bool {
+    match key {
+      Value::Integer(value) => target.parse::<i64>().is_ok_and(|t| t == *value),
+      Value::Float(value) => target.parse::<f64>().is_ok_and(|t| t == *value),
+      Value::Text(value) => value == target,
+      Value::Blob(_) | Value::Integer128(_) | Value::Null => false,
+    }
+  }
+
+  /// Orders the (textual) probe value against an index key column - `Less`
+  /// means the probe sorts before the key - coercing the probe to the key's
+  /// own type first. Used to pick which child subtrees a descent should follow.
+  fn index_key_cmp(key: &Value, target: &str) -> std::cmp::Ordering {
+    match key {
+      Value::Integer(value) => target
+        .parse::<i64>()
+        .map(|t| t.cmp(value))
+        .unwrap_or(std::cmp::Ordering::Less),
+      Value::Float(value) => target
+        .parse::<f64>()
+        .ok()
+        .and_then(|t| t.partial_cmp(value))
+        .unwrap_or(std::cmp::Ordering::Less),
+      Value::Text(value) => target.cmp(value.as_str()),
+      Value::Blob(_) | Value::Integer128(_) | Value::Null => std::cmp::Ordering::Less,
+    }
+  }
+
+  /// Resolves a `WHERE col = value` predicate to the matching rowids by
+  /// descending the index b-tree rooted at `page_num`: on each interior
+  /// (`0x02`) page, the probe key is compared against each cell's key
+  /// payload in order: cells whose key sorts before the probe are skipped,
+  /// a cell whose key equals the probe contributes its own rowid and has its
+  /// `left_child_page` searched, and the first cell whose key sorts after the
+  /// probe ends the scan with a descent into its `left_child_page` (or the
+  /// right-most pointer is followed once the probe sorts at or past every
+  /// cell). On a leaf (`0x0A`) page, key-matching cells contribute their
+  /// rowid. This prunes the search instead of visiting every leaf in the
+  /// index, while still finding duplicate keys spread over several subtrees.
+  fn rowids_from_index(&mut self, page_num: u32, target: &str) -> anyhow::Result<Vec<u64>> {
+    let btree = self.btree_from_page(page_num)?;
+    match btree.header.page_type {
+      0x0A => {
+        let mut rowids = Vec::new();
+        for cell in &btree.cells {
+          let full_payload = self.get_full_payload(cell)?;
+          let record = Record::parse(&full_payload, self.db_header.text_encoding())?;
+          if Self::index_key_matches(&record.values[0], target) {
+            rowids.push(record.values.last().unwrap().as_integer() as u64);
+          }
+        }
+        Ok(rowids)
+      }
+      0x02 => {
+        let mut rowids = Vec::new();
+        for cell in &btree.cells {
+          let Cell::IndexInterior {
+            left_child_page, ..
+          } = cell
+          else {
+            unreachable!("0x02 page only contains IndexInterior cells");
+          };
+          let full_payload = self.get_full_payload(cell)?;
+          let record = Record::parse(&full_payload, self.db_header.text_encoding())?;
+          match Self::index_key_cmp(&record.values[0], target) {
+            std::cmp::Ordering::Less => {
+              rowids.extend(self.rowids_from_index(*left_child_page, target)?);
+              return Ok(rowids);
+            }
+            std::cmp::Ordering::Equal => {
+              rowids.extend(self.rowids_from_index(*left_child_page, target)?);
+              rowids.push(record.values.last().unwrap().as_integer() as u64);
+            }
+            std::cmp::Ordering::Greater => continue,
+          }
+        }
+        let right_most_pointer = btree
+          .header
+          .right_most_pointer
+          .expect("interior page must carry a right-most pointer");
+        rowids.extend(self.rowids_from_index(right_most_pointer, target)?);
+        Ok(rowids)
+      }
+      _ => anyhow::bail!(
+        "Unexpected page type 0x{:02X} for page {}",
+        btree.header.page_type,
+        page_num
+      ),
+    }
+  }
+
+  /// Given the optional `WHERE` clause of a query against `table_name`,
+  /// returns the candidate rowids resolved through a matching index, or
+  /// `None` if the predicate isn't an indexed equality (in which case the
+  /// caller should fall back to a full table scan).
+  fn rowids_via_index(
+    &mut self,
+    table_name: &str,
+    where_clause: &Option<Condition>,
+  ) -> anyhow::Result<Option<Vec<u64>>> {
+    let Some(condition) = where_clause else {
+      return Ok(None);
+    };
+    if condition.operator != "=" {
+      return Ok(None);
+    }
+    let Some(index_root) = self.find_index(table_name, &condition.left)? else {
+      return Ok(None);
+    };
+    let target = condition
+      .right
+      .strip_prefix('\'')
+      .and_then(|s| s.strip_suffix('\''))
+      .unwrap_or(&condition.right);
+    Ok(Some(self.rowids_from_index(index_root, target)?))
+  }
+
+  /// Point-lookup of a single row by rowid, descending the table b-tree by
+  /// comparing `target_rowid` against each interior cell's key (the maximum
+  /// rowid in its left subtree) instead of visiting every leaf.
+  fn find_table_row(&mut self, page_num: u32, target_rowid: u64) -> anyhow::Result<Option<Cell>> {
+    let btree = self.btree_from_page(page_num)?;
+    match btree.header.page_type {
+      0x0D => Ok(btree.cells.into_iter().find(
+        |cell| matches!(cell, Cell::TableLeaf { row_id, .. } if *row_id == target_rowid),
+      )),
+      0x05 => {
+        for cell in &btree.cells {
+          if let Cell::TableInterior {
+            left_child_page,
+            row_id,
+          } = cell
+          {
+            if target_rowid <= *row_id {
+              return self.find_table_row(*left_child_page, target_rowid);
+            }
+          }
+        }
+        let right_most_pointer = btree
+          .header
+          .right_most_pointer
+          .expect("interior page must carry a right-most pointer");
+        self.find_table_row(right_most_pointer, target_rowid)
+      }
+      _ => anyhow::bail!(
+        "Unexpected page type 0x{:02X} for page {}",
+        btree.header.page_type,
+        page_num
+      ),
+    }
+  }
+
   fn get_table_schema(
     &mut self,
     table_name: &str,
   ) -> anyhow::Result<(HashMap<String, usize>, Option<String>)> {
-    let schema_btree = self.btree_from_page(1)?;
+    let schema_cells = self.table_leaf_cells(1)?;
     let table_name_upper = table_name.to_uppercase();
 
-    for cell in &schema_btree.cells {
+    for cell in &schema_cells {
       if let Cell::TableLeaf { .. } = cell {
         let record = self.record_from_cell(cell)?;
         if record.values[2].as_text().to_uppercase() == table_name_upper
@@ -67,47 +357,63 @@ impl SQLite {
     anyhow::bail!("Table schema not found for: {}", table_name)
   }
 
+  /// Reconstructs a cell's complete payload, following the overflow page
+  /// chain when the local portion doesn't hold everything. Each overflow
+  /// page starts with a 4-byte big-endian "next overflow page" pointer (0
+  /// means this is the last one) followed by up to `page_size - 4` bytes of
+  /// content, so callers must skip those 4 bytes before appending.
   pub fn get_full_payload(&mut self, cell: &Cell) -> anyhow::Result<Vec<u8>> {
-    match cell {
+    let (payload, overflow_page, payload_size) = match cell {
       Cell::TableLeaf {
         payload,
         overflow_page,
         payload_size,
         ..
-      } => {
-        let mut full_payload = payload.clone();
-        let mut current_overflow = *overflow_page;
-        let total_size = *payload_size as usize;
-
-        while let Some(page_num) = current_overflow {
-          let overflow_page = self.load_page(page_num)?;
-          let remaining_size = total_size - full_payload.len();
-          let data_size = std::cmp::min(remaining_size - 4, overflow_page.data.len() - 4);
-          full_payload.extend_from_slice(&overflow_page.read_bytes(0, data_size));
-          current_overflow = if remaining_size > data_size + 4 {
-            Some(overflow_page.read_u32(data_size))
-          } else {
-            None
-          };
-        }
-        Ok(full_payload)
       }
-      _ => Ok(cell.payload().to_vec()),
+      | Cell::IndexLeaf {
+        payload,
+        overflow_page,
+        payload_size,
+        ..
+      }
+      | Cell::IndexInterior {
+        payload,
+        overflow_page,
+        payload_size,
+        ..
+      } => (payload, overflow_page, payload_size),
+      Cell::TableInterior { .. } => return Ok(cell.payload().to_vec()),
+    };
+
+    let mut full_payload = payload.clone();
+    let mut current_overflow = *overflow_page;
+    let total_size = *payload_size as usize;
+
+    while let Some(page_num) = current_overflow {
+      let overflow_page = self.load_page(page_num)?;
+      let remaining_size = total_size - full_payload.len();
+      let data_size = std::cmp::min(remaining_size, overflow_page.data.len() - 4);
+      full_payload.extend_from_slice(&overflow_page.read_bytes(4, data_size));
+
+      let next_page = overflow_page.read_u32(0);
+      current_overflow = if next_page != 0 { Some(next_page) } else { None };
     }
+    Ok(full_payload)
   }
 
   pub fn list_tables(&mut self) -> anyhow::Result<()> {
-    let btree = self.btree_from_page(1)?;
-    for (i, cell) in btree.cells.iter().enumerate() {
+    let schema_cells = self.table_leaf_cells(1)?;
+    let cell_count = schema_cells.len();
+    for (i, cell) in schema_cells.iter().enumerate() {
       if let Cell::TableLeaf { .. } = cell {
         let full_payload = self.get_full_payload(cell)?;
-        let record = Record::parse(&full_payload)?;
+        let record = Record::parse(&full_payload, self.db_header.text_encoding())?;
         if record.values[0].as_text() == "table" && record.values[2].as_text() != "sqlite_sequence"
         {
           print!(
             "{}{}",
             record.values[2].as_text(),
-            if i < btree.cells.len() - 1 { " " } else { "" }
+            if i < cell_count - 1 { " " } else { "" }
           );
         }
       }
@@ -117,10 +423,10 @@ impl SQLite {
   }
 
   pub fn select_columns(&mut self, stmt: &SelectStatement) -> anyhow::Result<()> {
-    let schema_btree = self.btree_from_page(1)?;
+    let schema_cells = self.table_leaf_cells(1)?;
     let mut rootpage = None;
     let from_upper = stmt.from.to_uppercase();
-    for cell in &schema_btree.cells {
+    for cell in &schema_cells {
       if let Cell::TableLeaf { .. } = cell {
         let record = self.record_from_cell(cell)?;
         if record.values[2].as_text().to_uppercase() == from_upper {
@@ -131,18 +437,50 @@ impl SQLite {
     }
 
     let root_page = rootpage.ok_or_else(|| anyhow::anyhow!("Table not found: {}", stmt.from))?;
-    let btree = self.btree_from_page(root_page)?;
     let (column_map, rowid_alias) = self.get_table_schema(&stmt.from)?;
 
+    let where_clause = stmt
+      .where_clause
+      .as_ref()
+      .map(|cond| ResolvedCondition::resolve(cond, &column_map, &rowid_alias))
+      .transpose()?;
+
+    let order_by = stmt
+      .order_by
+      .as_ref()
+      .map(|order| ResolvedOrderBy::resolve(order, &column_map, &rowid_alias))
+      .transpose()?;
+
+    let indexed_rowids = self.rowids_via_index(&stmt.from, &stmt.where_clause)?;
+    let table_cells = match &indexed_rowids {
+      Some(rowids) => {
+        debug!("Using index to resolve {} candidate row(s)", rowids.len());
+        rowids
+          .iter()
+          .filter_map(|rowid| self.find_table_row(root_page, *rowid).transpose())
+          .collect::<anyhow::Result<Vec<_>>>()?
+      }
+      None => self.table_leaf_cells(root_page)?,
+    };
+
     match stmt.columns.as_slice() {
       [Column::Count] => {
         let count = self.count_rows_in_btree(root_page)?;
         println!("{count}");
         return Ok(());
       }
+      [Column::Aggregate(func, name)] => {
+        let position = resolve_column_position(name, &column_map, &rowid_alias)?;
+        let result = self.compute_aggregate(*func, position, &table_cells, &where_clause)?;
+        println!("{result}");
+        return Ok(());
+      }
       cols if cols.iter().any(|c| matches!(c, Column::Count)) => {
         anyhow::bail!("COUNT(*) must be the only column in the query");
       }
+      cols if cols.iter().any(|c| matches!(c, Column::Aggregate(..))) => {
+        anyhow::bail!("An aggregate function must be the only column in the query");
+      }
       cols => {
         let has_all = cols.iter().any(|c| matches!(c, Column::All));
         let column_positions: Vec<(String, usize)> = if has_all {
@@ -160,26 +498,21 @@ impl SQLite {
           cols
             .iter()
             .map(|col| match col {
-              Column::Named(name) => {
-                let name_upper = name.to_uppercase();
-                if let Some(alias) = &rowid_alias {
-                  if name_upper == alias.to_uppercase() || name_upper == "ROWID" {
-                    return Ok((name.clone(), usize::MAX));
-                  }
-                }
-                column_map
-                  .iter()
-                  .find(|(k, _)| k.to_uppercase() == name_upper)
-                  .map(|(k, &pos)| (k.clone(), pos))
-                  .ok_or_else(|| anyhow::anyhow!("Unknown column: {}", name))
-              }
-              _ => unreachable!("Count and All handled above"),
+              Column::Named(name) => resolve_column_position(name, &column_map, &rowid_alias)
+                .map(|pos| (name.clone(), pos)),
+              _ => unreachable!("Count, All and Aggregate handled above"),
             })
             .collect::<anyhow::Result<Vec<_>>>()?
         };
 
         debug!("Column positions: {:?}", column_positions);
-        self.print_rows(&btree, &column_positions, &rowid_alias)?;
+        self.print_rows(
+          &table_cells,
+          &column_positions,
+          &where_clause,
+          &order_by,
+          &stmt.limit,
+        )?;
       }
     }
 
@@ -188,11 +521,67 @@ impl SQLite {
 
   fn print_rows(
     &mut self,
-    btree: &BTree,
+    cells: &[Cell],
     column_positions: &[(String, usize)],
-    _rowid_alias: &Option<String>,
+    where_clause: &Option<ResolvedCondition>,
+    order_by: &Option<ResolvedOrderBy>,
+    limit: &Option<LimitClause>,
   ) -> anyhow::Result<()> {
-    for cell in &btree.cells {
+    let mut rows = self.matching_rows(cells, where_clause)?;
+
+    if let Some(order) = order_by {
+      rows.sort_by(|(a_id, a_record), (b_id, b_record)| {
+        let ordering = match order.position {
+          usize::MAX => a_id.cmp(b_id),
+          pos => match (a_record.values.get(pos + 1), b_record.values.get(pos + 1)) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (None, None) => std::cmp::Ordering::Equal,
+            (None, Some(_)) => std::cmp::Ordering::Less,
+            (Some(_), None) => std::cmp::Ordering::Greater,
+          },
+        };
+        if order.descending {
+          ordering.reverse()
+        } else {
+          ordering
+        }
+      });
+    }
+
+    let rows: Box<dyn Iterator<Item = (u64, Record)>> = match limit {
+      Some(limit) => Box::new(rows.into_iter().skip(limit.offset).take(limit.limit)),
+      None => Box::new(rows.into_iter()),
+    };
+
+    for (row_id, record) in rows {
+      let mut row = Vec::new();
+
+      for (name, pos) in column_positions {
+        trace!("Processing column: {} at position: {}", name, pos);
+        if *pos == usize::MAX {
+          row.push(row_id.to_string());
+        } else if *pos < record.values.len() {
+          row.push(record.values[*pos + 1].to_string());
+        } else {
+          row.push("NULL".to_string());
+        }
+      }
+      trace!("Row output: {:?}", row);
+      println!("{}", row.join("|"));
+    }
+    Ok(())
+  }
+
+  /// Decodes every `TableLeaf` cell and keeps the ones satisfying
+  /// `where_clause`, pairing each with its rowid. Shared by `print_rows` and
+  /// aggregate evaluation so both see the same filtered row set.
+  fn matching_rows(
+    &mut self,
+    cells: &[Cell],
+    where_clause: &Option<ResolvedCondition>,
+  ) -> anyhow::Result<Vec<(u64, Record)>> {
+    let mut rows = Vec::new();
+    for cell in cells {
       if let Cell::TableLeaf { row_id, .. } = cell {
         let record = self.record_from_cell(cell)?;
         trace!(
@@ -204,22 +593,124 @@ impl SQLite {
             .map(|v| v.to_string())
             .collect::<Vec<_>>()
         );
-        let mut row = Vec::new();
-
-        for (name, pos) in column_positions {
-          trace!("Processing column: {} at position: {}", name, pos);
-          if *pos == usize::MAX {
-            row.push(row_id.to_string());
-          } else if *pos < record.values.len() {
-            row.push(record.values[*pos + 1].to_string());
-          } else {
-            row.push("NULL".to_string());
+
+        if let Some(condition) = where_clause {
+          if !condition.matches(&record, *row_id) {
+            continue;
           }
         }
-        trace!("Row output: {:?}", row);
-        println!("{}", row.join("|"));
+
+        rows.push((*row_id, record));
       }
     }
+    Ok(rows)
+  }
+
+  /// Evaluates a single aggregate function over the rows matching
+  /// `where_clause`, in one pass over the decoded column values.
+  fn compute_aggregate(
+    &mut self,
+    func: AggregateFn,
+    position: usize,
+    cells: &[Cell],
+    where_clause: &Option<ResolvedCondition>,
+  ) -> anyhow::Result<String> {
+    let rows = self.matching_rows(cells, where_clause)?;
+    let values: Vec<Value> = rows
+      .into_iter()
+      .map(|(row_id, record)| {
+        if position == usize::MAX {
+          Value::Integer(row_id as i64)
+        } else {
+          record
+            .values
+            .get(position + 1)
+            .cloned()
+            .unwrap_or(Value::Null)
+        }
+      })
+      .collect();
+
+    match func {
+      AggregateFn::Count => Ok(values.iter().filter(|v| !matches!(v, Value::Null)).count().to_string()),
+      AggregateFn::Min => Ok(
+        values
+          .iter()
+          .filter(|v| !matches!(v, Value::Null))
+          .min_by(|&a, &b| a.cmp(b))
+          .map(|v| v.to_string())
+          .unwrap_or_else(|| "NULL".to_string()),
+      ),
+      AggregateFn::Max => Ok(
+        values
+          .iter()
+          .filter(|v| !matches!(v, Value::Null))
+          .max_by(|&a, &b| a.cmp(b))
+          .map(|v| v.to_string())
+          .unwrap_or_else(|| "NULL".to_string()),
+      ),
+      AggregateFn::Sum => {
+        let non_null = values.iter().filter(|v| !matches!(v, Value::Null));
+        if values
+          .iter()
+          .all(|v| matches!(v, Value::Integer(_) | Value::Null))
+        {
+          let sum: i64 = non_null.map(|v| v.as_integer()).sum();
+          Ok(sum.to_string())
+        } else {
+          let sum: f64 = non_null.map(as_f64).sum();
+          Ok(sum.to_string())
+        }
+      }
+      AggregateFn::Avg => {
+        let non_null: Vec<f64> = values
+          .iter()
+          .filter(|v| !matches!(v, Value::Null))
+          .map(as_f64)
+          .collect();
+        if non_null.is_empty() {
+          Ok("NULL".to_string())
+        } else {
+          let avg = non_null.iter().sum::<f64>() / non_null.len() as f64;
+          Ok(avg.to_string())
+        }
+      }
+    }
+  }
+
+  /// Walks the freelist trunk-page chain starting at
+  /// `db_header.first_freelist_trunk_page`, following each trunk's
+  /// next-trunk pointer until it hits 0, and returns every free page number
+  /// (trunk pages included, since a trunk page is itself a free page).
+  pub fn freelist_pages(&mut self) -> anyhow::Result<Vec<u32>> {
+    let mut pages = Vec::new();
+    let mut next_trunk = self.db_header.first_freelist_trunk_page;
+
+    while next_trunk != 0 {
+      let page = self.load_page(next_trunk)?;
+      let trunk = FreelistTrunkPage::parse(&page);
+      pages.push(next_trunk);
+      pages.extend(&trunk.leaf_pages);
+      next_trunk = trunk.next_trunk_page;
+    }
+
+    if pages.len() != self.db_header.total_freelist_pages as usize {
+      warn!(
+        "Freelist page count mismatch: walked {} but header reports {}",
+        pages.len(),
+        self.db_header.total_freelist_pages
+      );
+    }
+
+    Ok(pages)
+  }
+
+  pub fn print_freelist(&mut self) -> anyhow::Result<()> {
+    let pages = self.freelist_pages()?;
+    println!(
+      "{}",
+      pages.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(" ")
+    );
     Ok(())
   }
 
@@ -236,9 +727,9 @@ impl SQLite {
       .last()
       .ok_or_else(|| anyhow::anyhow!("Invalid query: no table name found"))?;
 
-    let schema_btree = self.btree_from_page(1)?;
+    let schema_cells = self.table_leaf_cells(1)?;
     let mut rootpage = None;
-    for cell in &schema_btree.cells {
+    for cell in &schema_cells {
       if let Cell::TableLeaf { .. } = cell {
         let record = self.record_from_cell(cell)?;
         if record.values[2].as_text() == table_name {
@@ -255,7 +746,7 @@ impl SQLite {
 
   fn record_from_cell(&mut self, cell: &Cell) -> anyhow::Result<Record> {
     let full_payload = self.get_full_payload(cell)?;
-    Record::parse(&full_payload)
+    Record::parse(&full_payload, self.db_header.text_encoding())
   }
 
   fn count_rows_in_btree(&mut self, page_num: u32) -> anyhow::Result<usize> {
@@ -289,3 +780,135 @@ impl SQLite {
     }
   }
 }
+
+/// A `WHERE` condition with its `left` side resolved against a table's schema,
+/// so each row can be tested without re-resolving the column name every time.
+struct ResolvedCondition {
+  /// `usize::MAX` means the condition targets the rowid alias, mirroring the
+  /// sentinel used for `column_positions` in `select_columns`.
+  position: usize,
+  operator: String,
+  right: String,
+}
+
+/// Resolves a column name to its index into `Record::values` (offset by the
+/// rowid at `values[0]`, see `print_rows`), or `usize::MAX` if it names the
+/// table's rowid alias.
+fn resolve_column_position(
+  name: &str,
+  column_map: &HashMap<String, usize>,
+  rowid_alias: &Option<String>,
+) -> anyhow::Result<usize> {
+  let name_upper = name.to_uppercase();
+  if let Some(alias) = rowid_alias {
+    if name_upper == alias.to_uppercase() || name_upper == "ROWID" {
+      return Ok(usize::MAX);
+    }
+  }
+
+  column_map
+    .iter()
+    .find(|(k, _)| k.to_uppercase() == name_upper)
+    .map(|(_, &pos)| pos)
+    .ok_or_else(|| anyhow::anyhow!("Unknown column: {}", name))
+}
+
+impl ResolvedCondition {
+  fn resolve(
+    condition: &Condition,
+    column_map: &HashMap<String, usize>,
+    rowid_alias: &Option<String>,
+  ) -> anyhow::Result<Self> {
+    let position = resolve_column_position(&condition.left, column_map, rowid_alias)?;
+
+    // String literals in SQL are single-quoted; strip them so `'5'` and `5` compare the same way.
+    let right = condition
+      .right
+      .strip_prefix('\'')
+      .and_then(|s| s.strip_suffix('\''))
+      .unwrap_or(&condition.right)
+      .to_string();
+
+    Ok(Self {
+      position,
+      operator: condition.operator.clone(),
+      right,
+    })
+  }
+
+  fn matches(&self, record: &Record, row_id: u64) -> bool {
+    if self.position == usize::MAX {
+      return match self.right.parse::<i64>() {
+        Ok(right) => Self::compare_numeric(row_id as i64, right, &self.operator),
+        Err(_) => false,
+      };
+    }
+
+    let Some(value) = record.values.get(self.position + 1) else {
+      return false;
+    };
+
+    match value {
+      Value::Integer(left) => match self.right.parse::<i64>() {
+        Ok(right) => Self::compare_numeric(*left, right, &self.operator),
+        Err(_) => false,
+      },
+      Value::Float(left) => match self.right.parse::<f64>() {
+        Ok(right) => Self::compare_numeric(*left, right, &self.operator),
+        Err(_) => false,
+      },
+      Value::Text(left) => Self::compare_text(left, &self.right, &self.operator),
+      Value::Blob(_) | Value::Integer128(_) | Value::Null => false,
+    }
+  }
+
+  fn compare_numeric<T: PartialOrd + PartialEq>(left: T, right: T, operator: &str) -> bool {
+    match operator {
+      "=" => left == right,
+      "<" => left < right,
+      ">" => left > right,
+      "<=" => left <= right,
+      ">=" => left >= right,
+      _ => false,
+    }
+  }
+
+  fn compare_text(left: &str, right: &str, operator: &str) -> bool {
+    match operator {
+      "=" => left == right,
+      "<" => left < right,
+      ">" => left > right,
+      "<=" => left <= right,
+      ">=" => left >= right,
+      _ => false,
+    }
+  }
+}
+
+/// A resolved `ORDER BY` clause; `position` uses the same `usize::MAX`
+/// sentinel for the rowid alias as `ResolvedCondition`.
+struct ResolvedOrderBy {
+  position: usize,
+  descending: bool,
+}
+
+impl ResolvedOrderBy {
+  fn resolve(
+    order_by: &parser::select::OrderBy,
+    column_map: &HashMap<String, usize>,
+    rowid_alias: &Option<String>,
+  ) -> anyhow::Result<Self> {
+    Ok(Self {
+      position: resolve_column_position(&order_by.column, column_map, rowid_alias)?,
+      descending: order_by.descending,
+    })
+  }
+}
+
+fn as_f64(value: &Value) -> f64 {
+  match value {
+    Value::Integer(v) => *v as f64,
+    Value::Float(v) => *v,
+    _ => 0.0,
+  }
+}
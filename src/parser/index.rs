@@ -0,0 +1,77 @@
+use miette::{Result, SourceSpan};
+use nom::{
+  IResult, Parser,
+  bytes::complete::{tag, tag_no_case, take_while1},
+  character::complete::multispace0,
+  combinator::map,
+  sequence::{delimited, preceded},
+};
+
+use super::error::SqlError;
+
+#[derive(Debug, PartialEq)]
+pub struct IndexStatement {
+  pub index_name: String,
+  pub table_name: String,
+  pub column: String,
+}
+
+fn ws(input: &str) -> IResult<&str, &str> {
+  multispace0(input)
+}
+
+fn identifier(input: &str) -> IResult<&str, String> {
+  let quoted = delimited(tag("\""), take_while1(|c: char| c != '"'), tag("\""));
+  let unquoted = take_while1(|c: char| c.is_alphanumeric() || c == '_');
+  map(nom::branch::alt((quoted, unquoted)), String::from).parse(input)
+}
+
+fn create_index(input: &str) -> IResult<&str, IndexStatement> {
+  map(
+    (
+      tag_no_case("CREATE"),
+      ws,
+      tag_no_case("INDEX"),
+      ws,
+      identifier,
+      ws,
+      tag_no_case("ON"),
+      ws,
+      identifier,
+      ws,
+      delimited(preceded(ws, tag("(")), preceded(ws, identifier), preceded(ws, tag(")"))),
+    ),
+    |(_, _, _, _, index_name, _, _, _, table_name, _, column)| IndexStatement {
+      index_name,
+      table_name,
+      column,
+    },
+  )
+  .parse(input)
+}
+
+pub fn parse(sql: &str) -> Result<IndexStatement> {
+  match create_index(sql) {
+    Ok((remaining, result)) => {
+      if !remaining.trim().is_empty() {
+        let offset = sql.len() - remaining.len();
+        Err(miette::Report::new(SqlError {
+          message: format!("Unparsed input remaining: '{}'", remaining),
+          input: sql.to_string(),
+          span: SourceSpan::new(offset.into(), remaining.len()),
+        }))
+      } else {
+        Ok(result)
+      }
+    }
+    Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+      let offset = sql.len() - e.input.len();
+      Err(miette::Report::new(SqlError {
+        message: "(index) Invalid SQL syntax".to_string(),
+        input: sql.to_string(),
+        span: SourceSpan::new(offset.into(), 1),
+      }))
+    }
+    Err(nom::Err::Incomplete(_)) => Err(miette::miette!("Incomplete input")),
+  }
+}
@@ -0,0 +1,4 @@
+pub mod error;
+pub mod index;
+pub mod schema;
+pub mod select;
@@ -2,8 +2,8 @@ use miette::{Result, SourceSpan};
 use nom::{
   IResult, Parser,
   branch::alt,
-  bytes::complete::{tag_no_case, take_while1},
-  character::complete::multispace0,
+  bytes::complete::{tag, tag_no_case, take_while1},
+  character::complete::{digit1, multispace0},
   combinator::{map, opt},
   multi::separated_list1,
   sequence::{delimited, preceded},
@@ -14,6 +14,8 @@ pub struct SelectStatement {
   pub columns: Vec<Column>,
   pub from: String,
   pub where_clause: Option<Condition>,
+  pub order_by: Option<OrderBy>,
+  pub limit: Option<LimitClause>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -21,13 +23,35 @@ pub enum Column {
   All,
   Count,
   Named(String),
+  Aggregate(AggregateFn, String),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AggregateFn {
+  Min,
+  Max,
+  Sum,
+  Avg,
+  Count,
 }
 
 #[derive(Debug, PartialEq)]
 pub struct Condition {
-  left: String,
-  operator: String,
-  right: String,
+  pub left: String,
+  pub operator: String,
+  pub right: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct OrderBy {
+  pub column: String,
+  pub descending: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LimitClause {
+  pub limit: usize,
+  pub offset: usize,
 }
 
 use super::error::SqlError;
@@ -46,7 +70,23 @@ fn identifier(input: &str) -> IResult<&str, String> {
   let (rest, ident) = take_while1(|c: char| c.is_alphanumeric() || c == '_')(input)?;
   let ident_upper = ident.to_uppercase();
   // Prevent reserved keywords from being used as identifiers
-  if matches!(ident_upper.as_str(), "SELECT" | "FROM" | "WHERE" | "COUNT") {
+  if matches!(
+    ident_upper.as_str(),
+    "SELECT"
+      | "FROM"
+      | "WHERE"
+      | "COUNT"
+      | "MIN"
+      | "MAX"
+      | "SUM"
+      | "AVG"
+      | "ORDER"
+      | "BY"
+      | "LIMIT"
+      | "OFFSET"
+      | "ASC"
+      | "DESC"
+  ) {
     return Err(nom::Err::Error(nom::error::Error::new(
       input,
       nom::error::ErrorKind::Verify,
@@ -56,6 +96,30 @@ fn identifier(input: &str) -> IResult<&str, String> {
   Ok((rest, ident.to_string()))
 }
 
+fn aggregate_fn(input: &str) -> IResult<&str, AggregateFn> {
+  alt((
+    map(tag_no_case("MIN"), |_| AggregateFn::Min),
+    map(tag_no_case("MAX"), |_| AggregateFn::Max),
+    map(tag_no_case("SUM"), |_| AggregateFn::Sum),
+    map(tag_no_case("AVG"), |_| AggregateFn::Avg),
+    map(tag_no_case("COUNT"), |_| AggregateFn::Count),
+  ))
+  .parse(input)
+}
+
+fn aggregate_column(input: &str) -> IResult<&str, Column> {
+  map(
+    (
+      aggregate_fn,
+      ws(tag("(")),
+      identifier,
+      ws(tag(")")),
+    ),
+    |(func, _, column, _)| Column::Aggregate(func, column),
+  )
+  .parse(input)
+}
+
 fn column(input: &str) -> IResult<&str, Column> {
   alt((
     map(tag_no_case("*"), |_| Column::All),
@@ -63,6 +127,7 @@ fn column(input: &str) -> IResult<&str, Column> {
       preceded(ws(tag_no_case("COUNT")), ws(tag_no_case("(*)"))),
       |_| Column::Count,
     ),
+    aggregate_column,
     map(identifier, Column::Named),
   ))
   .parse(input)
@@ -97,6 +162,36 @@ fn condition(input: &str) -> IResult<&str, Condition> {
     })
 }
 
+fn order_by_clause(input: &str) -> IResult<&str, OrderBy> {
+  map(
+    preceded(
+      (ws(tag_no_case("ORDER")), ws(tag_no_case("BY"))),
+      (identifier, opt(ws(alt((tag_no_case("ASC"), tag_no_case("DESC")))))),
+    ),
+    |(column, direction)| OrderBy {
+      column,
+      descending: matches!(direction, Some(d) if d.eq_ignore_ascii_case("DESC")),
+    },
+  )
+  .parse(input)
+}
+
+fn limit_clause(input: &str) -> IResult<&str, LimitClause> {
+  map(
+    preceded(
+      ws(tag_no_case("LIMIT")),
+      (digit1, opt(preceded(ws(tag_no_case("OFFSET")), digit1))),
+    ),
+    |(limit, offset): (&str, Option<&str>)| LimitClause {
+      limit: limit.parse().expect("digit1 guarantees a valid number"),
+      offset: offset
+        .map(|o| o.parse().expect("digit1 guarantees a valid number"))
+        .unwrap_or(0),
+    },
+  )
+  .parse(input)
+}
+
 fn select_statement(input: &str) -> IResult<&str, SelectStatement> {
   map(
     (
@@ -105,11 +200,15 @@ fn select_statement(input: &str) -> IResult<&str, SelectStatement> {
       ws(tag_no_case("FROM")),
       identifier,
       opt(preceded(ws(tag_no_case("WHERE")), condition)),
+      opt(order_by_clause),
+      opt(limit_clause),
     ),
-    |(_, columns, _, from, where_clause)| SelectStatement {
+    |(_, columns, _, from, where_clause, order_by, limit)| SelectStatement {
       columns,
       from,
       where_clause,
+      order_by,
+      limit,
     },
   )
   .parse(input)
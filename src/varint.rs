@@ -5,35 +5,62 @@ pub struct VarInt {
 }
 
 impl VarInt {
+  /// Number of bytes (1-9) needed to encode `value` as a SQLite varint: up
+  /// to 7 bits per byte for the first 8 bytes, and all 8 bits of the 9th.
   pub fn get_encoded_size(value: u64) -> usize {
-    match (value as usize).count_ones() {
-      0..=7 => 1,
-      8..=15 => 2,
-      _ => 5,
+    for size in 1..=8 {
+      if value < 1u64 << (7 * size) {
+        return size;
+      }
     }
+    9
   }
 
-  pub fn decode(data: &[u8]) -> u64 {
-    let mut value = u64::from_be_bytes([0; 8]);
-    for i in data.iter() {
-      value *= 128;
-      value += *i as u64;
+  /// Decodes a SQLite varint (1-9 bytes, big-endian, high bit of each of the
+  /// first 8 bytes signals continuation, the 9th byte contributes all 8 of
+  /// its bits) and returns the decoded value together with the number of
+  /// bytes consumed, so callers parsing record headers know where the next
+  /// field begins.
+  pub fn decode(data: &[u8]) -> (u64, usize) {
+    let mut value: u64 = 0;
+
+    for (i, &byte) in data.iter().take(9).enumerate() {
+      if i == 8 {
+        value = (value << 8) | byte as u64;
+        return (value, 9);
+      }
 
-      if (value & !0xFF) == 0 {
-        break;
+      value = (value << 7) | (byte & 0x7F) as u64;
+      if byte & 0x80 == 0 {
+        return (value, i + 1);
       }
     }
 
-    value
+    (value, data.len())
   }
 
   pub fn encode(value: u64) -> Vec<u8> {
-    let mut data = vec![];
-    let mut value = value;
-    while value > 0 {
-      data.push((value & 0x7F) as u8);
-      value >>= 7;
+    let size = Self::get_encoded_size(value);
+    let mut data = vec![0u8; size];
+
+    if size == 9 {
+      data[8] = value as u8;
+      let mut remaining = value >> 8;
+      for i in (0..8).rev() {
+        data[i] = (remaining & 0x7F) as u8 | 0x80;
+        remaining >>= 7;
+      }
+    } else {
+      let mut remaining = value;
+      for i in (0..size).rev() {
+        data[i] = (remaining & 0x7F) as u8;
+        remaining >>= 7;
+        if i != size - 1 {
+          data[i] |= 0x80;
+        }
+      }
     }
+
     data
   }
 }